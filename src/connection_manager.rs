@@ -1,11 +1,163 @@
-use std::net::TcpStream;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixStream;
 use std::time::{Duration, Instant};
 use std::io::Result as StdResult;
 use std::sync::Arc;
+use std::thread;
 
+use rand::Rng;
+
+// `register_direct`/`read_reply`/`send_color_warm`/`send_image` are written against
+// `HyperionTransport` (see below) rather than a concrete `TcpStream`, so the same
+// handshake/send code works unchanged over TCP or a Unix domain socket.
 use crate::hyperion::{register_direct, read_reply, send_color_warm, send_image};
 use crate::diagnostics::DiagnosticLogger;
 
+/// The `Read + Write` + timeout surface that the Hyperion handshake and frame-send
+/// helpers need from a socket. Implemented for both `TcpStream` and `UnixStream` (via
+/// `Transport`) so `HyperionConnectionManager`'s state machine, backoff, and health
+/// checks work unchanged regardless of which transport `ConnectionConfig.address`
+/// resolves to.
+pub trait HyperionTransport: Read + Write {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> StdResult<()>;
+    fn set_write_timeout(&self, dur: Option<Duration>) -> StdResult<()>;
+    fn read_timeout(&self) -> StdResult<Option<Duration>>;
+    fn write_timeout(&self) -> StdResult<Option<Duration>>;
+}
+
+impl HyperionTransport for TcpStream {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> StdResult<()> {
+        TcpStream::set_read_timeout(self, dur)
+    }
+    fn set_write_timeout(&self, dur: Option<Duration>) -> StdResult<()> {
+        TcpStream::set_write_timeout(self, dur)
+    }
+    fn read_timeout(&self) -> StdResult<Option<Duration>> {
+        TcpStream::read_timeout(self)
+    }
+    fn write_timeout(&self) -> StdResult<Option<Duration>> {
+        TcpStream::write_timeout(self)
+    }
+}
+
+impl HyperionTransport for UnixStream {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> StdResult<()> {
+        UnixStream::set_read_timeout(self, dur)
+    }
+    fn set_write_timeout(&self, dur: Option<Duration>) -> StdResult<()> {
+        UnixStream::set_write_timeout(self, dur)
+    }
+    fn read_timeout(&self) -> StdResult<Option<Duration>> {
+        UnixStream::read_timeout(self)
+    }
+    fn write_timeout(&self) -> StdResult<Option<Duration>> {
+        UnixStream::write_timeout(self)
+    }
+}
+
+/// A connected Hyperion socket, either a TCP connection or a local Unix domain
+/// socket. Local Hyperion instances commonly expose a Unix socket, which avoids
+/// TCP/loopback overhead for a 30 FPS stream running on the same box.
+pub enum Transport {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> StdResult<usize> {
+        match self {
+            Transport::Tcp(s) => s.read(buf),
+            Transport::Unix(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> StdResult<usize> {
+        match self {
+            Transport::Tcp(s) => s.write(buf),
+            Transport::Unix(s) => s.write(buf),
+        }
+    }
+    fn flush(&mut self) -> StdResult<()> {
+        match self {
+            Transport::Tcp(s) => s.flush(),
+            Transport::Unix(s) => s.flush(),
+        }
+    }
+}
+
+impl HyperionTransport for Transport {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> StdResult<()> {
+        match self {
+            Transport::Tcp(s) => HyperionTransport::set_read_timeout(s, dur),
+            Transport::Unix(s) => HyperionTransport::set_read_timeout(s, dur),
+        }
+    }
+    fn set_write_timeout(&self, dur: Option<Duration>) -> StdResult<()> {
+        match self {
+            Transport::Tcp(s) => HyperionTransport::set_write_timeout(s, dur),
+            Transport::Unix(s) => HyperionTransport::set_write_timeout(s, dur),
+        }
+    }
+    fn read_timeout(&self) -> StdResult<Option<Duration>> {
+        match self {
+            Transport::Tcp(s) => HyperionTransport::read_timeout(s),
+            Transport::Unix(s) => HyperionTransport::read_timeout(s),
+        }
+    }
+    fn write_timeout(&self) -> StdResult<Option<Duration>> {
+        match self {
+            Transport::Tcp(s) => HyperionTransport::write_timeout(s),
+            Transport::Unix(s) => HyperionTransport::write_timeout(s),
+        }
+    }
+}
+
+impl Transport {
+    /// Connect using `address`, dispatching on its scheme: `tcp://host:port`,
+    /// `unix:///path/to.sock`, or a bare `host:port` (treated as `tcp://`, for
+    /// backward compatibility with existing configs).
+    ///
+    /// The TCP path is bounded by `timeout`, so a firewalled/blackholed sink fails
+    /// fast instead of blocking on the OS connect timeout — which otherwise would
+    /// hold up `ConnectionPool::broadcast_image`, since it waits for every sink's
+    /// send to finish before returning. A Unix domain socket connect is local and either succeeds or fails
+    /// (`ECONNREFUSED`) immediately, so it has no equivalent bound.
+    fn connect(address: &str, timeout: Duration) -> StdResult<Transport> {
+        if let Some(path) = address.strip_prefix("unix://") {
+            return Ok(Transport::Unix(UnixStream::connect(path)?));
+        }
+
+        let host_port = address.strip_prefix("tcp://").unwrap_or(address);
+        let addr = host_port
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address found for host"))?;
+        Ok(Transport::Tcp(TcpStream::connect_timeout(&addr, timeout)?))
+    }
+}
+
+/// How `HyperionConnectionManager` spaces out reconnection attempts after a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectStrategy {
+    /// Always wait `initial_backoff_ms` between attempts.
+    FixedInterval,
+    /// `initial_backoff_ms * 2^(failures - 1)`, capped at `max_backoff_ms`.
+    ExponentialBackoff,
+    /// Decorrelated jitter (AWS architecture blog's "full jitter" successor):
+    /// `next = min(max_backoff_ms, rand(initial_backoff_ms, prev * 3))`, `prev = next`.
+    /// Spreads retries across reconnecting clients instead of lockstepping them.
+    DecorrelatedJitter,
+}
+
+impl Default for ReconnectStrategy {
+    fn default() -> Self {
+        ReconnectStrategy::ExponentialBackoff
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ConnectionConfig {
     pub address: String,
@@ -14,6 +166,8 @@ pub struct ConnectionConfig {
     pub max_backoff_ms: u64,
     pub connection_timeout_ms: u64,
     pub health_check_interval_ms: u64,
+    pub heartbeat_timeout_ms: u64,
+    pub reconnect_strategy: ReconnectStrategy,
 }
 
 impl Default for ConnectionConfig {
@@ -25,6 +179,8 @@ impl Default for ConnectionConfig {
             max_backoff_ms: 5000,
             connection_timeout_ms: 3000,
             health_check_interval_ms: 30000, // 30 seconds
+            heartbeat_timeout_ms: 1000,
+            reconnect_strategy: ReconnectStrategy::ExponentialBackoff,
         }
     }
 }
@@ -39,7 +195,7 @@ pub enum ConnectionState {
 
 pub struct HyperionConnectionManager {
     config: ConnectionConfig,
-    socket: Option<TcpStream>,
+    socket: Option<Transport>,
     state: ConnectionState,
     last_connection_attempt: Option<Instant>,
     consecutive_failures: u32,
@@ -47,6 +203,15 @@ pub struct HyperionConnectionManager {
     last_health_check: Instant,
     total_reconnections: u32,
     connection_start_time: Option<Instant>,
+    jitter_prev_backoff_ms: u64,
+    /// When the current backoff period ends, computed once per failure by
+    /// `handle_connection_failure` rather than recomputed on every
+    /// `ensure_connected` poll (several times a second, once per popped
+    /// frame) — `DecorrelatedJitter` draws a new random value and mutates
+    /// `jitter_prev_backoff_ms` each time it's called, so recomputing it
+    /// per-poll raced `jitter_prev_backoff_ms` to `max_backoff_ms` almost
+    /// immediately instead of growing once per actual failure.
+    next_retry_at: Option<Instant>,
 }
 
 impl HyperionConnectionManager {
@@ -58,6 +223,8 @@ impl HyperionConnectionManager {
             println!("No logger provided to connection manager");
         }
         
+        let jitter_prev_backoff_ms = config.initial_backoff_ms;
+
         HyperionConnectionManager {
             config,
             socket: None,
@@ -68,11 +235,13 @@ impl HyperionConnectionManager {
             last_health_check: Instant::now(),
             total_reconnections: 0,
             connection_start_time: None,
+            jitter_prev_backoff_ms,
+            next_retry_at: None,
         }
     }
     
     /// Ensure we have a healthy connection, reconnecting if necessary
-    pub fn ensure_connected(&mut self) -> StdResult<&mut TcpStream> {
+    pub fn ensure_connected(&mut self) -> StdResult<&mut Transport> {
         // Check if we need to perform a health check
         if self.last_health_check.elapsed() >= Duration::from_millis(self.config.health_check_interval_ms) {
             self.perform_health_check();
@@ -92,19 +261,19 @@ impl HyperionConnectionManager {
                 self.attempt_connection()?;
             }
             ConnectionState::Reconnecting => {
-                // Check if enough time has passed for retry
-                if let Some(last_attempt) = self.last_connection_attempt {
-                    let backoff_duration = self.calculate_backoff_duration();
-                    if last_attempt.elapsed() >= backoff_duration {
-                        self.attempt_connection()?;
-                    } else {
+                // Check if enough time has passed for retry, against the
+                // backoff duration computed once when the failure happened
+                // rather than recomputed on every poll (see `next_retry_at`).
+                match self.next_retry_at {
+                    Some(retry_at) if Instant::now() < retry_at => {
                         return Err(std::io::Error::new(
                             std::io::ErrorKind::NotConnected,
                             "Still in backoff period"
                         ));
                     }
-                } else {
-                    self.attempt_connection()?;
+                    _ => {
+                        self.attempt_connection()?;
+                    }
                 }
             }
         }
@@ -132,7 +301,8 @@ impl HyperionConnectionManager {
                                            self.config.max_retries));
         }
         
-        match TcpStream::connect(&self.config.address) {
+        let connect_timeout = Duration::from_millis(self.config.connection_timeout_ms);
+        match Transport::connect(&self.config.address, connect_timeout) {
             Ok(mut socket) => {
                 // Set socket timeout
                 socket.set_read_timeout(Some(Duration::from_millis(self.config.connection_timeout_ms)))?;
@@ -144,6 +314,7 @@ impl HyperionConnectionManager {
                         self.socket = Some(socket);
                         self.state = ConnectionState::Connected;
                         self.consecutive_failures = 0;
+                        self.reset_backoff_state();
                         self.connection_start_time = Some(Instant::now());
                         
                         if self.total_reconnections > 0 {
@@ -173,7 +344,7 @@ impl HyperionConnectionManager {
     }
     
     /// Perform Hyperion protocol handshake
-    fn perform_handshake(&self, socket: &mut TcpStream) -> StdResult<()> {
+    fn perform_handshake(&self, socket: &mut Transport) -> StdResult<()> {
         // Register with Hyperion
         register_direct(socket)?;
         read_reply(socket, false)?;
@@ -198,7 +369,8 @@ impl HyperionConnectionManager {
         } else {
             self.state = ConnectionState::Reconnecting;
             let backoff_duration = self.calculate_backoff_duration();
-            
+            self.next_retry_at = Some(Instant::now() + backoff_duration);
+
             if let Some(ref logger) = self.logger {
                 logger.log_warning(&format!("Hyperion connection failed (attempt {} of {}): {}. Retrying in {}ms", 
                                           self.consecutive_failures, 
@@ -209,14 +381,34 @@ impl HyperionConnectionManager {
         }
     }
     
-    /// Calculate exponential backoff duration
-    fn calculate_backoff_duration(&self) -> Duration {
-        let backoff_ms = std::cmp::min(
-            self.config.initial_backoff_ms * (2_u64.pow(self.consecutive_failures.saturating_sub(1))),
-            self.config.max_backoff_ms
-        );
+    /// Calculate the delay before the next reconnection attempt, per `ReconnectStrategy`.
+    fn calculate_backoff_duration(&mut self) -> Duration {
+        let backoff_ms = match self.config.reconnect_strategy {
+            ReconnectStrategy::FixedInterval => self.config.initial_backoff_ms,
+            ReconnectStrategy::ExponentialBackoff => std::cmp::min(
+                self.config.initial_backoff_ms * (2_u64.pow(self.consecutive_failures.saturating_sub(1))),
+                self.config.max_backoff_ms
+            ),
+            ReconnectStrategy::DecorrelatedJitter => {
+                let upper = self.jitter_prev_backoff_ms.saturating_mul(3).max(self.config.initial_backoff_ms);
+                let next = if upper <= self.config.initial_backoff_ms {
+                    self.config.initial_backoff_ms
+                } else {
+                    rand::thread_rng().gen_range(self.config.initial_backoff_ms..=upper)
+                };
+                let next = std::cmp::min(next, self.config.max_backoff_ms);
+                self.jitter_prev_backoff_ms = next;
+                next
+            }
+        };
         Duration::from_millis(backoff_ms)
     }
+
+    /// Reset the reconnect-strategy state that depends on consecutive failure count.
+    fn reset_backoff_state(&mut self) {
+        self.jitter_prev_backoff_ms = self.config.initial_backoff_ms;
+        self.next_retry_at = None;
+    }
     
     /// Handle network errors during operation
     pub fn handle_network_error(&mut self, error: &std::io::Error) -> bool {
@@ -239,36 +431,59 @@ impl HyperionConnectionManager {
             
             // Reset consecutive failures for network errors (not connection failures)
             self.consecutive_failures = 0;
+            self.reset_backoff_state();
         }
-        
+
         should_reconnect
     }
     
-    /// Perform periodic health check
+    /// Perform periodic health check by sending a lightweight Hyperion command and
+    /// waiting for a reply within `heartbeat_timeout_ms`. This catches a half-open
+    /// connection (server stopped reading/writing but the OS hasn't torn down the
+    /// socket yet), which `peer_addr()` alone can never detect.
     fn perform_health_check(&mut self) {
-        if let ConnectionState::Connected = self.state {
-            if let Some(ref socket) = self.socket {
-                // Simple check - try to get socket peer address
-                match socket.peer_addr() {
-                    Ok(_) => {
-                        // Connection appears healthy
-                        if let Some(ref logger) = self.logger {
-                            if let Some(start_time) = self.connection_start_time {
-                                let uptime = start_time.elapsed();
-                                logger.log("HYPERION", &format!("Connection healthy (uptime: {}s)", uptime.as_secs()));
-                            }
-                        }
-                    }
-                    Err(_) => {
-                        // Connection is broken
-                        if let Some(ref logger) = self.logger {
-                            logger.log_warning("Health check failed - connection appears broken");
-                        }
-                        self.socket = None;
-                        self.state = ConnectionState::Disconnected;
-                    }
+        if !matches!(self.state, ConnectionState::Connected) {
+            return;
+        }
+
+        let healthy = if let Some(ref mut socket) = self.socket {
+            let heartbeat_timeout = Duration::from_millis(self.config.heartbeat_timeout_ms);
+            let prev_read_timeout = socket.read_timeout().ok().flatten();
+            let prev_write_timeout = socket.write_timeout().ok().flatten();
+
+            // Re-register as the heartbeat payload rather than `send_color_warm`: it's
+            // a control-plane message with no visual effect, unlike Color, which would
+            // override the live Image stream with solid warm-white at the same
+            // priority on every health check interval.
+            let result = socket
+                .set_read_timeout(Some(heartbeat_timeout))
+                .and_then(|_| socket.set_write_timeout(Some(heartbeat_timeout)))
+                .and_then(|_| register_direct(socket))
+                .and_then(|_| read_reply(socket, false));
+
+            // Restore the steady-state timeouts regardless of outcome.
+            let _ = socket.set_read_timeout(prev_read_timeout);
+            let _ = socket.set_write_timeout(prev_write_timeout);
+
+            result.is_ok()
+        } else {
+            false
+        };
+
+        if healthy {
+            if let Some(ref logger) = self.logger {
+                if let Some(start_time) = self.connection_start_time {
+                    let uptime = start_time.elapsed();
+                    logger.log("HYPERION", &format!("Heartbeat ok (uptime: {}s)", uptime.as_secs()));
                 }
             }
+        } else {
+            if let Some(ref logger) = self.logger {
+                logger.log_warning("Heartbeat failed - no reply before deadline, connection appears dead");
+            }
+            self.socket = None;
+            self.state = ConnectionState::Disconnected;
+            self.total_reconnections += 1;
         }
     }
     
@@ -305,18 +520,20 @@ impl HyperionConnectionManager {
             }
             self.state = ConnectionState::Disconnected;
             self.consecutive_failures = 0;
+            self.reset_backoff_state();
         }
     }
-    
+
     /// Force reconnection (useful for testing or manual recovery)
     pub fn force_reconnect(&mut self) {
         if let Some(ref logger) = self.logger {
             logger.log("HYPERION", "Forcing reconnection");
         }
-        
+
         self.socket = None;
         self.state = ConnectionState::Disconnected;
         self.consecutive_failures = 0;
+        self.reset_backoff_state();
     }
     
     /// Try to send image with fallback behavior
@@ -370,4 +587,83 @@ pub struct ConnectionStats {
     pub total_reconnections: u32,
     pub uptime_seconds: u64,
     pub last_attempt_ago_ms: u64,
+}
+
+/// Fans a single captured frame out to several independent Hyperion/HyperHDR sinks
+/// (e.g. front and back bias lighting), each with its own `HyperionConnectionManager`
+/// and therefore its own backoff/failure/reconnection state. A dead or reconnecting
+/// sink never blocks or fails the others.
+pub struct ConnectionPool {
+    managers: Vec<HyperionConnectionManager>,
+}
+
+impl ConnectionPool {
+    pub fn new(managers: Vec<HyperionConnectionManager>) -> Self {
+        ConnectionPool { managers }
+    }
+
+    /// Send `img` to every sink in the pool, each on its own thread, so a sink
+    /// that's mid-reconnect (blocked in `attempt_connection` for up to
+    /// `connection_timeout_ms`) doesn't delay this frame's delivery to the others.
+    /// The call returns only once every sink has finished, so such a sink does
+    /// delay the next broadcast to all sinks by up to that timeout. Returns one
+    /// result per sink in the same order the managers were added, so callers can
+    /// tell which sink (if any) failed without one bad sink masking the others.
+    pub fn broadcast_image(&mut self, img: &image::RgbImage, verbose: bool) -> Vec<StdResult<bool>> {
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .managers
+                .iter_mut()
+                .map(|manager| scope.spawn(move || manager.send_image_with_fallback(img, verbose)))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(std::io::Error::new(std::io::ErrorKind::Other, "sink send thread panicked"))
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Aggregate per-sink stats into a pool-wide summary. `channel_depth` and
+    /// `dropped_frames` come from the capture/sender hand-off (`FrameChannel` in
+    /// `main.rs`), which is shared across every sink in the pool rather than
+    /// per-sink, so the caller passes them in instead of the pool owning a copy.
+    pub fn get_stats(&self, channel_depth: usize, dropped_frames: u64) -> PoolStats {
+        let per_sink: Vec<ConnectionStats> = self.managers.iter().map(|m| m.get_stats()).collect();
+        let connected_sinks = self.managers.iter().filter(|m| m.is_connected()).count();
+        let total_reconnections = per_sink.iter().map(|s| s.total_reconnections).sum();
+
+        PoolStats {
+            sink_count: self.managers.len(),
+            connected_sinks,
+            total_reconnections,
+            per_sink,
+            channel_depth,
+            dropped_frames,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.managers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.managers.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct PoolStats {
+    pub sink_count: usize,
+    pub connected_sinks: usize,
+    pub total_reconnections: u32,
+    pub per_sink: Vec<ConnectionStats>,
+    /// Frames currently queued in the capture→sender hand-off.
+    pub channel_depth: usize,
+    /// Frames the capture thread has dropped because the hand-off was full.
+    pub dropped_frames: u64,
 }
\ No newline at end of file
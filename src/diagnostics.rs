@@ -1,136 +1,554 @@
 use std::fs::{File, OpenOptions};
 use std::io::{Write, BufWriter};
-use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
+
+use hdrhistogram::Histogram;
+use regex::RegexSet;
+
+/// Critical-event patterns checked against Kodi log lines that already
+/// contain "ERROR", evaluated together in a single `RegexSet` pass instead
+/// of a chain of substring scans. `check_kodi_log_errors` maps the set's
+/// match indices back into this slice to report which pattern matched.
+const KODI_CRITICAL_PATTERNS: &[&str] = &[
+    r"(?i)drm",
+    r"(?i)freeze",
+    r"(?i)crash",
+    r"(?i)segfault",
+];
+
+fn kodi_critical_patterns() -> &'static RegexSet {
+    static PATTERNS: OnceLock<RegexSet> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        RegexSet::new(KODI_CRITICAL_PATTERNS).expect("valid Kodi critical-event patterns")
+    })
+}
+
+/// Minimal JSON string escaping for `StdoutFormat::Json` - quotes,
+/// backslashes, and control characters - without pulling in a JSON crate
+/// for one field.
+fn json_escape(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Relative importance of a log entry. Ordered so `severity >= min_severity`
+/// comparisons work directly; `Fatal` is the highest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    /// Parse the case-insensitive name a live-listener client sends in its
+    /// filter spec (`TRACE`/`DEBUG`/`INFO`/`WARN`/`ERROR`/`FATAL`).
+    pub fn from_name(name: &str) -> Option<Severity> {
+        match name.to_ascii_uppercase().as_str() {
+            "TRACE" => Some(Severity::Trace),
+            "DEBUG" => Some(Severity::Debug),
+            "INFO" => Some(Severity::Info),
+            "WARN" => Some(Severity::Warn),
+            "ERROR" => Some(Severity::Error),
+            "FATAL" => Some(Severity::Fatal),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Severity::Trace => "TRACE",
+            Severity::Debug => "DEBUG",
+            Severity::Info => "INFO",
+            Severity::Warn => "WARN",
+            Severity::Error => "ERROR",
+            Severity::Fatal => "FATAL",
+        }
+    }
+}
+
+/// How `DiagnosticLogger` echoes immediate-severity entries to stdout. The
+/// on-disk log file always uses the plain `[ts] +elapsedms [category] msg`
+/// line regardless of this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StdoutFormat {
+    /// `[ts] +elapsedms [category] message`, matching the file exactly.
+    PlainText,
+    /// Same line, ANSI-colored by severity/category when stdout is a tty
+    /// and `NO_COLOR` isn't set; falls back to `PlainText` otherwise.
+    Color,
+    /// One JSON object per line for downstream log aggregators.
+    Json,
+}
+
+/// Minimum severity, category allowlist, and optional pid a client asked
+/// for when it connected to the live log-listener socket (see
+/// `log_listener`). `categories: None` means "every category".
+#[derive(Clone)]
+pub struct LogFilterSpec {
+    pub min_severity: Severity,
+    pub categories: Option<HashSet<String>>,
+    pub pid: Option<u32>,
+}
+
+impl Default for LogFilterSpec {
+    fn default() -> Self {
+        LogFilterSpec {
+            min_severity: Severity::Trace,
+            categories: None,
+            pid: None,
+        }
+    }
+}
+
+impl LogFilterSpec {
+    fn matches(&self, severity: Severity, category: &str) -> bool {
+        if severity < self.min_severity {
+            return false;
+        }
+        // This process only ever logs its own entries, so a pid filter is a
+        // cheap way for a client to confirm it's talking to the instance it
+        // expects rather than an actual multi-process selector.
+        if let Some(want_pid) = self.pid {
+            if want_pid != std::process::id() {
+                return false;
+            }
+        }
+        match &self.categories {
+            Some(categories) => categories.contains(category),
+            None => true,
+        }
+    }
+}
+
+/// One listener's queue of formatted log lines, fed by `DiagnosticLogger`
+/// and drained by `log_listener`'s per-connection writer.
+struct Subscriber {
+    spec: LogFilterSpec,
+    sender: SyncSender<String>,
+    send_failures: u32,
+}
+
+/// Bound on how many formatted lines can be queued for a single listener
+/// before a full channel counts as a send failure, so a stalled remote
+/// client can't grow memory without bound.
+const LISTENER_QUEUE_CAPACITY: usize = 256;
+/// Consecutive full-queue failures before a listener is treated as dead and
+/// dropped, so a frozen client doesn't leak a subscriber slot forever.
+const LISTENER_MAX_SEND_FAILURES: u32 = 3;
+
+/// Handle returned by `DiagnosticLogger::subscribe`. The log-listener
+/// socket reads lines from `receiver` and writes them to its client.
+pub struct LogSubscription {
+    pub receiver: Receiver<String>,
+}
+
+/// Per-category allow/deny list, checked after the severity threshold.
+/// Default is `AllowAll`, matching the previous unfiltered behavior.
+#[derive(Clone)]
+pub enum CategoryFilter {
+    AllowAll,
+    Allow(HashSet<String>),
+    Deny(HashSet<String>),
+}
+
+impl CategoryFilter {
+    fn permits(&self, category: &str) -> bool {
+        match self {
+            CategoryFilter::AllowAll => true,
+            CategoryFilter::Allow(categories) => categories.contains(category),
+            CategoryFilter::Deny(categories) => !categories.contains(category),
+        }
+    }
+}
+
+impl Default for CategoryFilter {
+    fn default() -> Self {
+        CategoryFilter::AllowAll
+    }
+}
 
 #[derive(Clone)]
 pub struct LogEntry {
     timestamp: u128,
     elapsed: u128,
+    severity: Severity,
     category: String,
     message: String,
 }
 
+impl LogEntry {
+    fn format_line(&self) -> String {
+        format!("[{}] +{}ms [{}] {}", self.timestamp, self.elapsed, self.category, self.message)
+    }
+
+    fn byte_size(&self) -> usize {
+        self.category.len() + self.message.len()
+    }
+}
+
+/// Cap on `error_buffer`'s total retained message+category bytes. Bounding
+/// by bytes rather than entry count means a burst of long messages can't
+/// push out the short, high-frequency entries `dump_error_context` needs
+/// for a clear picture around an error, and quiet periods of short entries
+/// naturally retain more history.
+const ERROR_BUFFER_MAX_BYTES: usize = 4 * 1024 * 1024;
+
+/// FIFO history of buffered log entries, bounded by total message bytes.
+struct BoundedLogBuffer {
+    entries: VecDeque<LogEntry>,
+    total_bytes: usize,
+    max_bytes: usize,
+}
+
+impl BoundedLogBuffer {
+    fn new(max_bytes: usize) -> Self {
+        BoundedLogBuffer {
+            entries: VecDeque::new(),
+            total_bytes: 0,
+            max_bytes,
+        }
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        self.total_bytes += entry.byte_size();
+        self.entries.push_back(entry);
+
+        while self.total_bytes > self.max_bytes {
+            match self.entries.pop_front() {
+                Some(evicted) => self.total_bytes -= evicted.byte_size(),
+                None => break,
+            }
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+// Frame send latency is tracked in microseconds, 1us-10s with 3
+// significant figures, so p50/p90/p99 carry constant relative error across
+// the whole range without storing every sample.
+const LATENCY_HISTOGRAM_MIN_US: u64 = 1;
+const LATENCY_HISTOGRAM_MAX_US: u64 = 10_000_000;
+const LATENCY_HISTOGRAM_SIGFIG: u8 = 3;
+
 pub struct DiagnosticLogger {
     writer: Arc<Mutex<BufWriter<File>>>,
     start_time: SystemTime,
     last_summary: Arc<Mutex<SystemTime>>,
-    error_buffer: Arc<Mutex<VecDeque<LogEntry>>>,
+    error_buffer: Arc<Mutex<BoundedLogBuffer>>,
     capture_count: Arc<Mutex<u64>>,
     last_hyperion_error: Arc<Mutex<Option<SystemTime>>>,
+    capture_latency: Arc<Mutex<Histogram<u64>>>,
+    min_severity: Mutex<Severity>,
+    category_filter: Mutex<CategoryFilter>,
+    subscribers: Mutex<Vec<Subscriber>>,
+    stdout_format: StdoutFormat,
 }
 
 impl DiagnosticLogger {
-    pub fn new(log_path: &str) -> std::io::Result<Self> {
+    pub fn new(log_path: &str, stdout_format: StdoutFormat) -> std::io::Result<Self> {
         let file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(log_path)?;
-        
+
         let writer = Arc::new(Mutex::new(BufWriter::new(file)));
         let start_time = SystemTime::now();
-        
+
+        let capture_latency = Histogram::new_with_bounds(
+            LATENCY_HISTOGRAM_MIN_US,
+            LATENCY_HISTOGRAM_MAX_US,
+            LATENCY_HISTOGRAM_SIGFIG,
+        ).expect("valid histogram bounds");
+
         let logger = DiagnosticLogger {
             writer,
             start_time,
             last_summary: Arc::new(Mutex::new(start_time)),
-            error_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(100))),
+            error_buffer: Arc::new(Mutex::new(BoundedLogBuffer::new(ERROR_BUFFER_MAX_BYTES))),
             capture_count: Arc::new(Mutex::new(0)),
             last_hyperion_error: Arc::new(Mutex::new(None)),
+            capture_latency: Arc::new(Mutex::new(capture_latency)),
+            min_severity: Mutex::new(Severity::Trace),
+            category_filter: Mutex::new(CategoryFilter::default()),
+            subscribers: Mutex::new(Vec::new()),
+            stdout_format,
         };
-        
+
         // Log session start
-        logger.log_immediate("SESSION", "=== DRM GRABBER DIAGNOSTIC SESSION START ===");
+        logger.log_immediate(Severity::Info, "SESSION", "=== DRM GRABBER DIAGNOSTIC SESSION START ===");
         logger.log_system_info();
-        
+
         Ok(logger)
     }
+
+    /// Set the minimum severity that passes the filter. Entries below this
+    /// are dropped before they're formatted or buffered.
+    pub fn set_min_severity(&self, severity: Severity) {
+        *self.min_severity.lock().unwrap() = severity;
+    }
+
+    /// Replace the per-category allow/deny filter, e.g. to silence
+    /// `TRACK`/`PROC` noise while keeping `DRM_ERROR`/`HYPERION_ERROR`.
+    pub fn set_category_filter(&self, filter: CategoryFilter) {
+        *self.category_filter.lock().unwrap() = filter;
+    }
+
+    fn passes_filter(&self, severity: Severity, category: &str) -> bool {
+        if severity < *self.min_severity.lock().unwrap() {
+            return false;
+        }
+        self.category_filter.lock().unwrap().permits(category)
+    }
+
+    /// Register a live listener. The subscriber is added before the recent
+    /// buffer is replayed into it, so nothing logged concurrently with the
+    /// replay is lost (it may just arrive interleaved with the replay).
+    pub fn subscribe(&self, spec: LogFilterSpec) -> LogSubscription {
+        let (sender, receiver) = sync_channel(LISTENER_QUEUE_CAPACITY);
+        let replay_sender = sender.clone();
+
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(Subscriber {
+                spec: spec.clone(),
+                sender,
+                send_failures: 0,
+            });
+        }
+
+        if let Ok(buffer) = self.error_buffer.lock() {
+            for entry in buffer.iter() {
+                if spec.matches(entry.severity, &entry.category) {
+                    let _ = replay_sender.try_send(entry.format_line());
+                }
+            }
+        }
+
+        LogSubscription { receiver }
+    }
+
+    /// Fan a just-logged line out to every subscriber whose filter matches.
+    /// A full queue counts as a failure rather than blocking; a subscriber
+    /// that fails repeatedly is dropped so a stalled client can't pin memory.
+    fn publish_to_subscribers(&self, severity: Severity, category: &str, line: &str) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain_mut(|subscriber| {
+                if !subscriber.spec.matches(severity, category) {
+                    return true;
+                }
+
+                match subscriber.sender.try_send(line.to_string()) {
+                    Ok(()) => {
+                        subscriber.send_failures = 0;
+                        true
+                    }
+                    Err(TrySendError::Full(_)) => {
+                        subscriber.send_failures += 1;
+                        subscriber.send_failures < LISTENER_MAX_SEND_FAILURES
+                    }
+                    Err(TrySendError::Disconnected(_)) => false,
+                }
+            });
+        }
+    }
+
+    /// Record one frame send latency sample. `main` measures it from when the
+    /// sender takes a frame off the capture channel to when the broadcast to
+    /// every sink returns; time spent capturing the frame and waiting in the
+    /// channel is not included.
+    pub fn record_capture_latency(&self, latency: Duration) {
+        let micros = latency.as_micros().min(LATENCY_HISTOGRAM_MAX_US as u128) as u64;
+        if let Ok(mut histogram) = self.capture_latency.lock() {
+            let _ = histogram.record(micros.max(LATENCY_HISTOGRAM_MIN_US));
+        }
+    }
     
-    // Immediate logging for critical events
-    pub fn log_immediate(&self, category: &str, message: &str) {
+    // Immediate logging for critical events. Checks `passes_filter` itself
+    // rather than relying on every caller to have already checked it:
+    // several call sites below (SUMMARY/MILESTONE/CONTEXT/SYSTEM/
+    // HYPERION_ERROR/KODI_ERROR) call this directly instead of going
+    // through `log_with_severity`, and `set_min_severity`/
+    // `set_category_filter` need to gate those too.
+    pub fn log_immediate(&self, severity: Severity, category: &str, message: &str) {
+        if !self.passes_filter(severity, category) {
+            return;
+        }
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis();
-            
+
         let elapsed = SystemTime::now()
             .duration_since(self.start_time)
             .unwrap()
             .as_millis();
-            
-        let log_line = format!("[{}] +{}ms [{}] {}\n", 
-                              timestamp, elapsed, category, message);
-        
+
+        let line_body = format!("[{}] +{}ms [{}] {}", timestamp, elapsed, category, message);
+
         if let Ok(mut writer) = self.writer.lock() {
-            let _ = writer.write_all(log_line.as_bytes());
+            let _ = writer.write_all(format!("{}\n", line_body).as_bytes());
             let _ = writer.flush();
         }
-        
+
         // Print critical categories to stdout
-        if matches!(category, "ERROR" | "WARN" | "SESSION" | "INIT" | "CRASH" | "SUMMARY") {
-            print!("{}", log_line);
+        if matches!(category, "ERROR" | "WARN" | "SESSION" | "INIT" | "CRASH" | "SUMMARY")
+            || severity >= Severity::Warn
+        {
+            self.echo_to_stdout(severity, category, timestamp, elapsed, message, &line_body);
         }
+
+        self.publish_to_subscribers(severity, category, &line_body);
     }
-    
+
+    /// Echo one immediate entry to stdout in whichever `stdout_format` this
+    /// logger was constructed with.
+    fn echo_to_stdout(&self, severity: Severity, category: &str, timestamp: u128, elapsed: u128, message: &str, line_body: &str) {
+        match self.stdout_format {
+            StdoutFormat::PlainText => println!("{}", line_body),
+            StdoutFormat::Color => {
+                if Self::color_enabled() {
+                    println!("{}", Self::colorize(severity, category, line_body));
+                } else {
+                    println!("{}", line_body);
+                }
+            }
+            StdoutFormat::Json => println!(
+                "{{\"ts\":{},\"elapsed_ms\":{},\"severity\":\"{}\",\"category\":\"{}\",\"msg\":\"{}\"}}",
+                timestamp, elapsed, severity.name(), json_escape(category), json_escape(message)
+            ),
+        }
+    }
+
+    fn color_enabled() -> bool {
+        use std::io::IsTerminal;
+        std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+    }
+
+    // Red for ERROR/CRASH, yellow for WARN, green for SUMMARY/MILESTONE; any
+    // other category by severity: red for Error/Fatal, yellow for Warn, green
+    // for Info, dim below Info.
+    fn colorize(severity: Severity, category: &str, line_body: &str) -> String {
+        let ansi_code = match category {
+            "ERROR" | "CRASH" => "31",
+            "WARN" => "33",
+            "SUMMARY" | "MILESTONE" => "32",
+            _ => match severity {
+                Severity::Error | Severity::Fatal => "31",
+                Severity::Warn => "33",
+                Severity::Info => "32",
+                _ => "2",
+            },
+        };
+        format!("\x1b[{}m{}\x1b[0m", ansi_code, line_body)
+    }
+
     // Buffered logging for regular events (only written during summaries or errors)
-    pub fn log_buffered(&self, category: &str, message: &str) {
+    pub fn log_buffered(&self, severity: Severity, category: &str, message: &str) {
+        if !self.passes_filter(severity, category) {
+            return;
+        }
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis();
-            
+
         let elapsed = SystemTime::now()
             .duration_since(self.start_time)
             .unwrap()
             .as_millis();
-            
+
         let entry = LogEntry {
             timestamp,
             elapsed,
+            severity,
             category: category.to_string(),
             message: message.to_string(),
         };
-        
+
+        self.publish_to_subscribers(severity, category, &entry.format_line());
+
         if let Ok(mut buffer) = self.error_buffer.lock() {
-            buffer.push_back(entry);
-            // Keep only last 100 entries
-            if buffer.len() > 100 {
-                buffer.pop_front();
-            }
+            buffer.push(entry);
         }
     }
-    
-    // Smart logging - decides whether to log immediately or buffer
-    pub fn log(&self, category: &str, message: &str) {
+
+    /// Map a category to the severity `log` uses when a caller hasn't picked
+    /// one explicitly. `log_with_severity` then writes `Warn` and above
+    /// immediately (dumping the buffered context for `Error` and above) and
+    /// buffers the rest, so `Info` categories such as SESSION/INIT/SUMMARY
+    /// passed through `log` are buffered, not written immediately.
+    fn default_severity_for_category(category: &str) -> Severity {
         match category {
-            "ERROR" | "WARN" | "SESSION" | "INIT" | "CRASH" | "SUMMARY" => {
-                self.log_immediate(category, message);
-                // When we hit an error, dump the recent buffer
-                if category == "ERROR" {
-                    self.dump_error_context();
-                }
-            }
-            _ => {
-                self.log_buffered(category, message);
+            "ERROR" | "CRASH" | "DRM_ERROR" | "HYPERION_ERROR" | "KODI_ERROR"
+            | "CAPTURE_ERROR" | "OOM" => Severity::Error,
+            "WARN" | "MEMORY_PRESSURE" => Severity::Warn,
+            "SESSION" | "INIT" | "SUMMARY" | "MILESTONE" => Severity::Info,
+            _ => Severity::Debug,
+        }
+    }
+
+    // Smart logging - decides whether to log immediately or buffer, using a
+    // severity inferred from `category` for backward compatibility.
+    pub fn log(&self, category: &str, message: &str) {
+        self.log_with_severity(Self::default_severity_for_category(category), category, message);
+    }
+
+    /// Like `log`, but with an explicit severity. Entries below
+    /// `min_severity` or excluded by the category filter are dropped before
+    /// being formatted or buffered.
+    pub fn log_with_severity(&self, severity: Severity, category: &str, message: &str) {
+        if !self.passes_filter(severity, category) {
+            return;
+        }
+
+        if severity >= Severity::Warn {
+            self.log_immediate(severity, category, message);
+            // When we hit an error, dump the recent buffer
+            if severity >= Severity::Error {
+                self.dump_error_context();
             }
+        } else {
+            self.log_buffered(severity, category, message);
         }
     }
-    
+
     // Dump recent buffered events when an error occurs
     fn dump_error_context(&self) {
         if let Ok(buffer) = self.error_buffer.lock() {
             if !buffer.is_empty() {
-                self.log_immediate("CONTEXT", "--- Recent events before error ---");
+                self.log_immediate(Severity::Info, "CONTEXT", "--- Recent events before error ---");
                 for entry in buffer.iter() {
-                    let log_line = format!("[{}] +{}ms [{}] {}", 
-                                          entry.timestamp, entry.elapsed, 
-                                          entry.category, entry.message);
                     if let Ok(mut writer) = self.writer.lock() {
-                        let _ = writer.write_all(format!("{}\n", log_line).as_bytes());
+                        let _ = writer.write_all(format!("{}\n", entry.format_line()).as_bytes());
                     }
                 }
-                self.log_immediate("CONTEXT", "--- End recent events ---");
+                self.log_immediate(Severity::Info, "CONTEXT", "--- End recent events ---");
             }
         }
     }
@@ -154,9 +572,11 @@ impl DiagnosticLogger {
                 .unwrap_or(Duration::ZERO)
                 .as_secs() / 60;
             
-            self.log_immediate("SUMMARY", &format!("Running for {} minutes, {} captures completed", 
+            self.log_immediate(Severity::Info, "SUMMARY", &format!("Running for {} minutes, {} captures completed",
                                                   elapsed_mins, capture_count));
-            
+
+            self.log_capture_latency_summary();
+
             // Log current system state
             self.log_system_summary();
         }
@@ -178,7 +598,7 @@ impl DiagnosticLogger {
     pub fn log_hyperion_operation(&self, operation: &str, success: bool, details: &str) {
         if success {
             // Only log successful operations occasionally to reduce noise
-            self.log_buffered("HYPERION", &format!("{} -> SUCCESS", operation));
+            self.log_buffered(Severity::Debug, "HYPERION", &format!("{} -> SUCCESS", operation));
         } else {
             // Deduplicate Hyperion errors - only log if it's been a while since last error
             let should_log = {
@@ -198,7 +618,7 @@ impl DiagnosticLogger {
             };
             
             if should_log {
-                self.log_immediate("HYPERION_ERROR", &format!("{} -> FAILED ({})", operation, details));
+                self.log_immediate(Severity::Error, "HYPERION_ERROR", &format!("{} -> FAILED ({})", operation, details));
             }
         }
     }
@@ -214,10 +634,14 @@ impl DiagnosticLogger {
         self.maybe_log_summary();
     }
     
+    pub fn capture_count(&self) -> u64 {
+        *self.capture_count.lock().unwrap()
+    }
+
     pub fn log_capture_milestone(&self, count: u64) {
         // Log every 500 captures to track progress without spam
         if count % 500 == 0 {
-            self.log_immediate("MILESTONE", &format!("Completed {} captures", count));
+            self.log_immediate(Severity::Info, "MILESTONE", &format!("Completed {} captures", count));
         }
     }
     
@@ -236,19 +660,43 @@ impl DiagnosticLogger {
     fn log_system_info(&self) {
         // Log basic system information
         if let Ok(hostname) = std::env::var("HOSTNAME") {
-            self.log_immediate("SYSTEM", &format!("Hostname: {}", hostname));
+            self.log_immediate(Severity::Info, "SYSTEM", &format!("Hostname: {}", hostname));
         }
         
         // Log process info
-        self.log_immediate("SYSTEM", &format!("PID: {}", std::process::id()));
+        self.log_immediate(Severity::Info, "SYSTEM", &format!("PID: {}", std::process::id()));
         
         // Log Rust/Cargo version info
-        self.log_immediate("SYSTEM", "Built with Rust (version info not available)");
+        self.log_immediate(Severity::Info, "SYSTEM", "Built with Rust (version info not available)");
         
         // Log Kodi log path for monitoring
-        self.log_immediate("SYSTEM", "Kodi log path: /storage/.kodi/temp/kodi.log");
+        self.log_immediate(Severity::Info, "SYSTEM", "Kodi log path: /storage/.kodi/temp/kodi.log");
     }
     
+    // Report p50/p90/p99/max frame send latency, then reset so each
+    // summary reflects only the minute since the last one.
+    fn log_capture_latency_summary(&self) {
+        let mut histogram = match self.capture_latency.lock() {
+            Ok(histogram) => histogram,
+            Err(_) => return,
+        };
+
+        if histogram.len() == 0 {
+            return;
+        }
+
+        self.log_immediate(Severity::Info, "SUMMARY", &format!(
+            "Capture latency (us): p50={} p90={} p99={} max={} samples={}",
+            histogram.value_at_quantile(0.50),
+            histogram.value_at_quantile(0.90),
+            histogram.value_at_quantile(0.99),
+            histogram.max(),
+            histogram.len(),
+        ));
+
+        histogram.reset();
+    }
+
     fn log_system_summary(&self) {
         use std::process::Command;
         use std::fs;
@@ -256,7 +704,7 @@ impl DiagnosticLogger {
         // Log current load and memory
         if let Ok(loadavg) = fs::read_to_string("/proc/loadavg") {
             let load = loadavg.split_whitespace().next().unwrap_or("unknown");
-            self.log_immediate("SUMMARY", &format!("Load: {}", load));
+            self.log_immediate(Severity::Info, "SUMMARY", &format!("Load: {}", load));
         }
         
         if let Ok(meminfo) = fs::read_to_string("/proc/meminfo") {
@@ -273,7 +721,7 @@ impl DiagnosticLogger {
             
             if mem_total > 0 {
                 let mem_used_pct = ((mem_total - mem_available) * 100) / mem_total;
-                self.log_immediate("SUMMARY", &format!("Memory: {}%", mem_used_pct));
+                self.log_immediate(Severity::Info, "SUMMARY", &format!("Memory: {}%", mem_used_pct));
             }
         }
         
@@ -282,9 +730,9 @@ impl DiagnosticLogger {
             if result.status.success() {
                 let stdout_str = String::from_utf8_lossy(&result.stdout);
                 let pid_count = stdout_str.trim().lines().count();
-                self.log_immediate("SUMMARY", &format!("Kodi processes: {}", pid_count));
+                self.log_immediate(Severity::Info, "SUMMARY", &format!("Kodi processes: {}", pid_count));
             } else {
-                self.log_immediate("SUMMARY", "Kodi: NOT RUNNING");
+                self.log_immediate(Severity::Info, "SUMMARY", "Kodi: NOT RUNNING");
             }
         }
         
@@ -294,32 +742,41 @@ impl DiagnosticLogger {
     
     fn check_kodi_log_errors(&self) {
         use std::process::Command;
-        
+
         let kodi_log_paths = [
             "/storage/.kodi/temp/kodi.log",
             "/var/log/kodi.log",
             "/home/osmc/.kodi/temp/kodi.log",
         ];
-        
+
+        let patterns = kodi_critical_patterns();
+
         for log_path in &kodi_log_paths {
             if let Ok(output) = Command::new("tail")
                 .arg("-n")
                 .arg("20")
                 .arg(log_path)
                 .output() {
-                
+
                 let log_content = String::from_utf8_lossy(&output.stdout);
-                
-                // Look for critical errors
+
+                // Look for critical errors: an ERROR line mentioning any of
+                // the configured critical-event patterns, checked together
+                // in one pass instead of a chain of substring scans.
                 for line in log_content.lines() {
-                    if line.contains("ERROR") && (
-                        line.contains("drm") || 
-                        line.contains("DRM") || 
-                        line.contains("freeze") ||
-                        line.contains("crash") ||
-                        line.contains("segfault")
-                    ) {
-                        self.log_immediate("KODI_ERROR", &format!("From {}: {}", log_path, line));
+                    if !line.contains("ERROR") {
+                        continue;
+                    }
+
+                    let matched: Vec<&str> = patterns
+                        .matches(line)
+                        .into_iter()
+                        .map(|i| KODI_CRITICAL_PATTERNS[i])
+                        .collect();
+
+                    if !matched.is_empty() {
+                        self.log_immediate(Severity::Error, "KODI_ERROR", &format!(
+                            "From {} (matched {}): {}", log_path, matched.join(","), line));
                     }
                 }
             }
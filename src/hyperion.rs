@@ -0,0 +1,142 @@
+use std::io::Result as IoResult;
+
+use flatbuffers::FlatBufferBuilder;
+use image::RgbImage;
+
+use crate::connection_manager::HyperionTransport;
+use crate::hyperion_reply_generated::hyperionnet::root_as_reply;
+use crate::hyperion_request_generated::hyperionnet::{
+    Color, ColorArgs, Command, Image, ImageArgs, RawImage, RawImageArgs, Register, RegisterArgs,
+    Request, RequestArgs,
+};
+
+/// Priority source name we register under. Low-numbered priorities win, so we sit
+/// above Hyperion's own effects/background but below anything explicitly pinned
+/// higher by the user.
+const ORIGIN: &str = "drm-vc4-grabber";
+const PRIORITY: i32 = 150;
+
+/// Register this grabber with Hyperion as a priority source. Must be the first
+/// message sent on a freshly-connected socket.
+pub fn register_direct<T: HyperionTransport>(socket: &mut T) -> IoResult<()> {
+    let mut builder = FlatBufferBuilder::new();
+    let origin = builder.create_string(ORIGIN);
+    let register = Register::create(
+        &mut builder,
+        &RegisterArgs {
+            origin: Some(origin),
+            priority: PRIORITY,
+        },
+    );
+    let request = Request::create(
+        &mut builder,
+        &RequestArgs {
+            command_type: Command::Register,
+            command: Some(register.as_union_value()),
+        },
+    );
+    builder.finish_size_prefixed(request, None);
+
+    socket.write_all(builder.finished_data())
+}
+
+/// Read one size-prefixed `Reply` message from `socket` and surface any error
+/// Hyperion reported back as an `io::Error`. Used after the `Register` request in
+/// the handshake and in the heartbeat; frames are sent without reading a reply.
+pub fn read_reply<T: HyperionTransport>(socket: &mut T, verbose: bool) -> IoResult<()> {
+    let mut size_buf = [0u8; 4];
+    socket.read_exact(&mut size_buf)?;
+    let size = u32::from_be_bytes(size_buf) as usize;
+
+    let mut payload = vec![0u8; size];
+    socket.read_exact(&mut payload)?;
+
+    let reply = root_as_reply(&payload)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+
+    if let Some(error) = reply.error() {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, error.to_string()));
+    }
+
+    if verbose {
+        println!(
+            "Hyperion reply: video={:?} registered={:?}",
+            reply.video(),
+            reply.registered()
+        );
+    }
+
+    Ok(())
+}
+
+/// Send a dim warm-white `Color` command. The handshake sends it once after
+/// registering; its reply is not read. The periodic heartbeat in
+/// `perform_health_check` re-sends `register_direct` instead.
+pub fn send_color_warm<T: HyperionTransport>(socket: &mut T, verbose: bool) -> IoResult<()> {
+    const WARM_WHITE_RGB: i32 = 0xFF_E0_B0;
+
+    let mut builder = FlatBufferBuilder::new();
+    let color = Color::create(
+        &mut builder,
+        &ColorArgs {
+            data: WARM_WHITE_RGB,
+            duration: 200,
+        },
+    );
+    let request = Request::create(
+        &mut builder,
+        &RequestArgs {
+            command_type: Command::Color,
+            command: Some(color.as_union_value()),
+        },
+    );
+    builder.finish_size_prefixed(request, None);
+
+    if verbose {
+        println!("Sending warm color 0x{:06X}", WARM_WHITE_RGB);
+    }
+
+    socket.write_all(builder.finished_data())
+}
+
+/// Encode `img` as a Hyperion `Image` command and write it to `socket`.
+pub fn send_image<T: HyperionTransport>(socket: &mut T, img: &RgbImage, verbose: bool) -> IoResult<()> {
+    let (width, height) = img.dimensions();
+
+    let mut builder = FlatBufferBuilder::new();
+    let data = builder.create_vector(img.as_raw());
+    let raw_image = RawImage::create(
+        &mut builder,
+        &RawImageArgs {
+            data: Some(data),
+            width: width as i32,
+            height: height as i32,
+        },
+    );
+    let image = Image::create(
+        &mut builder,
+        &ImageArgs {
+            data: Some(raw_image),
+            duration: -1,
+        },
+    );
+    let request = Request::create(
+        &mut builder,
+        &RequestArgs {
+            command_type: Command::Image,
+            command: Some(image.as_union_value()),
+        },
+    );
+    builder.finish_size_prefixed(request, None);
+
+    if verbose {
+        println!(
+            "Sending image {}x{} ({} bytes)",
+            width,
+            height,
+            img.as_raw().len()
+        );
+    }
+
+    socket.write_all(builder.finished_data())
+}
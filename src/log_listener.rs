@@ -0,0 +1,110 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::os::unix::net::UnixListener;
+use std::sync::Arc;
+use std::thread;
+
+use crate::diagnostics::{DiagnosticLogger, LogFilterSpec, Severity};
+
+/// Either kind of socket this module accepts connections on, mirroring the
+/// `tcp://`/`unix://`/bare-address parsing `Transport` uses for outbound
+/// Hyperion connections in `connection_manager`.
+enum ListenerSocket {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+}
+
+/// Accepts connections on a Unix or TCP socket and streams filtered
+/// `DiagnosticLogger` entries to each client, Fuchsia-LogListener style: a
+/// client opens the socket, sends one filter-spec line, then just reads a
+/// replayed-then-live tail. Useful for watching the grabber during a
+/// display freeze where SSH + `tail -f` is impractical.
+pub struct LogListener;
+
+impl LogListener {
+    /// Bind `address` (`unix:///path/to.sock`, or `host:port`/`tcp://host:port`
+    /// for TCP) and spawn an accept loop that serves clients against `logger`
+    /// for the life of the process. Returns once the socket is bound;
+    /// connection handling happens on background threads so a stalled
+    /// client can never block the capture path.
+    pub fn spawn(address: &str, logger: Arc<DiagnosticLogger>) -> std::io::Result<()> {
+        let socket = Self::bind(address)?;
+
+        thread::spawn(move || match socket {
+            ListenerSocket::Tcp(listener) => {
+                for stream in listener.incoming().flatten() {
+                    let logger = Arc::clone(&logger);
+                    thread::spawn(move || Self::serve(stream, logger));
+                }
+            }
+            ListenerSocket::Unix(listener) => {
+                for stream in listener.incoming().flatten() {
+                    let logger = Arc::clone(&logger);
+                    thread::spawn(move || Self::serve(stream, logger));
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    fn bind(address: &str) -> std::io::Result<ListenerSocket> {
+        if let Some(path) = address.strip_prefix("unix://") {
+            let _ = std::fs::remove_file(path);
+            return Ok(ListenerSocket::Unix(UnixListener::bind(path)?));
+        }
+
+        let tcp_address = address.strip_prefix("tcp://").unwrap_or(address);
+        Ok(ListenerSocket::Tcp(TcpListener::bind(tcp_address)?))
+    }
+
+    /// Read one filter-spec line, subscribe, then stream matching lines
+    /// until the client disconnects or `DiagnosticLogger` drops it as dead.
+    fn serve<S: Read + Write>(stream: S, logger: Arc<DiagnosticLogger>) {
+        let mut reader = BufReader::new(stream);
+        let mut spec_line = String::new();
+        if reader.read_line(&mut spec_line).is_err() {
+            return;
+        }
+
+        let spec = parse_filter_spec(&spec_line);
+        let subscription = logger.subscribe(spec);
+        let mut stream = reader.into_inner();
+
+        for line in subscription.receiver {
+            if stream.write_all(line.as_bytes()).is_err() || stream.write_all(b"\n").is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// Parse the `key=value;key=value` filter-spec line a client sends right
+/// after connecting. Unknown keys are ignored; a blank or unparsable line
+/// yields the most permissive spec (every severity, every category, any pid).
+fn parse_filter_spec(line: &str) -> LogFilterSpec {
+    let mut spec = LogFilterSpec::default();
+
+    for field in line.trim().split(';') {
+        let mut parts = field.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts.next().unwrap_or("").trim();
+
+        match key {
+            "min_severity" => {
+                if let Some(severity) = Severity::from_name(value) {
+                    spec.min_severity = severity;
+                }
+            }
+            "categories" if !value.is_empty() => {
+                spec.categories = Some(value.split(',').map(|s| s.trim().to_string()).collect());
+            }
+            "pid" => {
+                spec.pid = value.parse().ok();
+            }
+            _ => {}
+        }
+    }
+
+    spec
+}
@@ -2,7 +2,6 @@
 extern crate nix;
 
 use std::fs::{File, OpenOptions};
-use std::net::TcpStream;
 use std::os::fd::AsFd;
 
 use clap::{App, Arg};
@@ -14,10 +13,10 @@ use drm_ffi::drm_set_client_cap;
 use dump_image::dump_framebuffer_to_image;
 use image::{ImageError, RgbImage};
 
+use std::collections::VecDeque;
 use std::os::unix::io::{AsRawFd, RawFd};
-use std::{thread, time::Duration};
-
-use std::io::Result as StdResult;
+use std::sync::{Arc, Condvar, Mutex};
+use std::{thread, time::{Duration, Instant}};
 
 pub mod ffi;
 pub mod framebuffer;
@@ -31,9 +30,20 @@ pub mod dump_image;
 pub mod diagnostics;
 pub mod system_monitor;
 pub mod connection_manager;
+pub mod metrics;
+pub mod log_listener;
 
 pub use hyperion_request_generated::hyperionnet::{Clear, Color, Command, Image, Register};
-use hyperion::{read_reply, register_direct, send_image};
+use connection_manager::{ConnectionConfig, ConnectionPool, HyperionConnectionManager};
+use diagnostics::{DiagnosticLogger, StdoutFormat};
+use log_listener::LogListener;
+use metrics::{MetricsEndpoint, MetricsSink};
+use system_monitor::SystemMonitor;
+
+/// How often `SystemMonitor` polls load/memory/DRM/process state.
+const MONITOR_INTERVAL_MS: u64 = 10_000;
+/// How often a configured `MetricsSink` flushes its batched InfluxDB points.
+const METRICS_FLUSH_INTERVAL_MS: u64 = 10_000;
 
 pub struct Card(File);
 
@@ -65,29 +75,81 @@ fn save_screenshot(img: &RgbImage) -> Result<(), ImageError> {
     img.save("screenshot.png")
 }
 
-fn send_dumped_image(socket: &mut TcpStream, img: &RgbImage, verbose: bool) -> StdResult<()> {
-    register_direct(socket)?;
-    read_reply(socket, verbose)?;
+/// Parse a `--metrics-endpoint` value into a `MetricsEndpoint`: `udp://host:port`
+/// for a fire-and-forget UDP datagram, or `http://host:port/db` (scheme optional)
+/// for a batched POST, mirroring the `tcp://`/`unix://`/bare parsing `Transport`
+/// uses for outbound Hyperion addresses.
+fn parse_metrics_endpoint(value: &str) -> Option<MetricsEndpoint> {
+    if let Some(rest) = value.strip_prefix("udp://") {
+        let (host, port) = rest.rsplit_once(':')?;
+        return Some(MetricsEndpoint::Udp {
+            host: host.to_string(),
+            port: port.parse().ok()?,
+        });
+    }
 
-    send_image(socket, img, verbose)?;
+    let rest = value.strip_prefix("http://").unwrap_or(value);
+    let (host_port, db) = rest.split_once('/')?;
+    let (host, port) = host_port.rsplit_once(':')?;
+    Some(MetricsEndpoint::Http {
+        host: host.to_string(),
+        port: port.parse().ok()?,
+        db: db.to_string(),
+    })
+}
 
-    Ok(())
+/// Bounded hand-off between the capture thread and the sender thread. Holds at
+/// most `capacity` frames; once full, `push` drops the oldest queued frame
+/// rather than blocking the producer, so the sender always works on the
+/// newest frame and a stalled/reconnecting sink never throttles capture.
+struct FrameChannel {
+    state: Mutex<FrameChannelState>,
+    frame_ready: Condvar,
 }
 
-fn dump_and_send_framebuffer(
-    socket: &mut TcpStream,
-    card: &Card,
-    fb: Handle,
-    verbose: bool,
-) -> StdResult<()> {
-    let img = dump_framebuffer_to_image(card, fb, verbose);
-    if let Ok(img) = img {
-        send_dumped_image(socket, &img, verbose)?;
-    } else if verbose {
-        eprintln!("Error dumping framebuffer to image.");
+struct FrameChannelState {
+    frames: VecDeque<RgbImage>,
+    capacity: usize,
+    dropped_frames: u64,
+}
+
+impl FrameChannel {
+    fn new(capacity: usize) -> Self {
+        FrameChannel {
+            state: Mutex::new(FrameChannelState {
+                frames: VecDeque::with_capacity(capacity),
+                capacity,
+                dropped_frames: 0,
+            }),
+            frame_ready: Condvar::new(),
+        }
+    }
+
+    fn push(&self, img: RgbImage) {
+        let mut state = self.state.lock().unwrap();
+        if state.frames.len() >= state.capacity {
+            state.frames.pop_front();
+            state.dropped_frames += 1;
+        }
+        state.frames.push_back(img);
+        self.frame_ready.notify_one();
+    }
+
+    fn pop_blocking(&self) -> RgbImage {
+        let mut state = self.state.lock().unwrap();
+        while state.frames.is_empty() {
+            state = self.frame_ready.wait(state).unwrap();
+        }
+        state.frames.pop_front().unwrap()
     }
 
-    Ok(())
+    fn depth(&self) -> usize {
+        self.state.lock().unwrap().frames.len()
+    }
+
+    fn dropped_frames(&self) -> u64 {
+        self.state.lock().unwrap().dropped_frames
+    }
 }
 
 fn find_framebuffer(card: &Card, verbose: bool) -> Option<Handle> {
@@ -126,9 +188,6 @@ fn find_framebuffer(card: &Card, verbose: bool) -> Option<Handle> {
     None
 }
 
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::Arc;
-
 fn main() {
     let matches = App::new("DRM VC4 Screen Grabber for Hyperion")
         .version("0.1.2")
@@ -148,7 +207,11 @@ fn main() {
                 .long("address")
                 .default_value("127.0.0.1:19400")
                 .takes_value(true)
-                .help("The Hyperion TCP socket address to send the captured screenshots to."),
+                .multiple(true)
+                .number_of_values(1)
+                .help("A Hyperion/HyperHDR address to send captured frames to: host:port, \
+                       tcp://host:port or unix:///path/to.sock. \
+                       Pass --address multiple times to broadcast to several servers at once."),
         )
         .arg(
             Arg::with_name("screenshot")
@@ -162,6 +225,27 @@ fn main() {
                 .long("verbose")
                 .help("Print verbose debugging information."),
         )
+        .arg(
+            Arg::with_name("log-path")
+                .long("log-path")
+                .default_value("drm-vc4-grabber.log")
+                .takes_value(true)
+                .help("Path to the diagnostic log file."),
+        )
+        .arg(
+            Arg::with_name("metrics-endpoint")
+                .long("metrics-endpoint")
+                .takes_value(true)
+                .help("InfluxDB endpoint to export system/capture metrics to, e.g. \
+                       http://host:8086/db or udp://host:8089. Omit to disable metrics export."),
+        )
+        .arg(
+            Arg::with_name("log-listen")
+                .long("log-listen")
+                .takes_value(true)
+                .help("Bind address for the live diagnostic log socket (tcp://host:port or \
+                       unix:///path/to.sock). Omit to disable."),
+        )
         .get_matches();
 
     let verbose = matches.is_present("verbose");
@@ -183,8 +267,12 @@ fn main() {
         drm_ffi::ioctl::set_cap(card.as_raw_fd(), &set_cap).unwrap();
     }
 
-    let address = matches.value_of("address").unwrap();
-    
+    let addresses: Vec<String> = matches
+        .values_of("address")
+        .unwrap()
+        .map(String::from)
+        .collect();
+
     if screenshot {
         if let Some(fb) = find_framebuffer(&card, verbose) {
             let img = dump_framebuffer_to_image(&card, fb, verbose).unwrap();
@@ -193,71 +281,129 @@ fn main() {
             println!("No framebuffer found!");
         }
     } else {
-        let mut socket = TcpStream::connect(address).unwrap();
-        register_direct(&mut socket).unwrap();
-        read_reply(&mut socket, verbose).unwrap();
+        let log_path = matches.value_of("log-path").unwrap();
+        let stdout_format = if verbose { StdoutFormat::Color } else { StdoutFormat::PlainText };
+        let logger = Arc::new(
+            DiagnosticLogger::new(log_path, stdout_format).expect("failed to open diagnostic log file"),
+        );
+
+        let metrics_sink: Option<Arc<MetricsSink>> = matches.value_of("metrics-endpoint").and_then(|endpoint| {
+            match parse_metrics_endpoint(endpoint) {
+                Some(parsed) => {
+                    let host_tag = std::env::var("HOSTNAME").unwrap_or_else(|_| "drm-vc4-grabber".to_string());
+                    Some(Arc::new(MetricsSink::new(
+                        parsed,
+                        host_tag,
+                        METRICS_FLUSH_INTERVAL_MS,
+                        Some(Arc::clone(&logger)),
+                    )))
+                }
+                None => {
+                    logger.log_warning(&format!("Invalid --metrics-endpoint value: {}", endpoint));
+                    None
+                }
+            }
+        });
 
-        if verbose {
-            println!("Connected to Hyperion, starting capture loop");
+        let monitor = SystemMonitor::new(Arc::clone(&logger), metrics_sink);
+        monitor.start_monitoring(MONITOR_INTERVAL_MS);
+
+        if let Some(listen_address) = matches.value_of("log-listen") {
+            if let Err(e) = LogListener::spawn(listen_address, Arc::clone(&logger)) {
+                logger.log_warning(&format!("Failed to start log listener on {}: {}", listen_address, e));
+            }
         }
 
-        // Track consecutive errors for connection reliability
-        let consecutive_errors = Arc::new(AtomicU32::new(0));
-        // Track consecutive "no framebuffer" occurrences
-        let mut no_fb_count: u32 = 0;
+        let mut pool = ConnectionPool::new(
+            addresses
+                .iter()
+                .map(|address| {
+                    let config = ConnectionConfig {
+                        address: address.clone(),
+                        ..ConnectionConfig::default()
+                    };
+                    HyperionConnectionManager::new(config, Some(Arc::clone(&logger)))
+                })
+                .collect(),
+        );
 
-        loop {
-            if let Some(fb) = find_framebuffer(&card, verbose) {
-                no_fb_count = 0; // Reset counter on successful find
-                match dump_and_send_framebuffer(&mut socket, &card, fb, verbose) {
-                    Ok(_) => {
-                        consecutive_errors.store(0, Ordering::Relaxed);
-                        thread::sleep(Duration::from_millis(33)); // ~30 FPS
-                    }
-                    Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {
-                        eprintln!("HyperHDR disconnected. Reconnecting...");
-                        consecutive_errors.store(0, Ordering::Relaxed);
-                        thread::sleep(Duration::from_secs(2));
-
-                        match TcpStream::connect(address) {
-                            Ok(new_socket) => {
-                                socket = new_socket;
-                                let _ = register_direct(&mut socket);
-                                let _ = read_reply(&mut socket, verbose);
-                                eprintln!("Reconnected to HyperHDR");
-                            }
-                            Err(e) => {
-                                eprintln!("Reconnection failed: {}. Will retry...", e);
+        if verbose {
+            println!("Starting capture loop, broadcasting to {} sink(s)", pool.len());
+        }
+
+        // Capacity 1: the frame being sent has already been popped, so the single
+        // queued slot always holds the newest capture.
+        let channel = Arc::new(FrameChannel::new(1));
+
+        // Capture thread: owns the DRM card and pushes frames into the bounded
+        // channel. This runs independently of send_image/reconnect backoff, so a
+        // slow or reconnecting Hyperion server can no longer stall capture.
+        let capture_channel = Arc::clone(&channel);
+        let capture_handle = thread::spawn(move || {
+            let mut no_fb_count: u32 = 0;
+
+            loop {
+                if let Some(fb) = find_framebuffer(&card, verbose) {
+                    no_fb_count = 0; // Reset counter on successful find
+
+                    match dump_framebuffer_to_image(&card, fb, verbose) {
+                        Ok(img) => capture_channel.push(img),
+                        Err(_) => {
+                            if verbose {
+                                eprintln!("Error dumping framebuffer to image.");
                             }
                         }
                     }
-                    Err(e) => {
-                        let errors = consecutive_errors.fetch_add(1, Ordering::Relaxed) + 1;
-
-                        if verbose {
-                            eprintln!("Capture error #{}: {}", errors, e);
-                        }
 
-                        // Back off on errors
-                        let backoff = match errors {
-                            1..=2 => 100,
-                            3..=5 => 500,
-                            _ => 2000,
-                        };
+                    thread::sleep(Duration::from_millis(33)); // ~30 FPS
+                } else {
+                    no_fb_count += 1;
 
-                        thread::sleep(Duration::from_millis(backoff));
+                    if verbose {
+                        eprintln!("No framebuffer found (count: {}), waiting...", no_fb_count);
                     }
+
+                    // Don't send any color - just wait silently
+                    // The LEDs will maintain their last state or timeout naturally
+                    thread::sleep(Duration::from_secs(1));
                 }
-            } else {
-                no_fb_count += 1;
+            }
+        });
+
+        // The capture thread's body is an infinite loop, so it should never return;
+        // if it does (almost always via a panic from one of the `.unwrap()`s in
+        // `find_framebuffer`), the sender thread below would otherwise hang forever
+        // in `channel.pop_blocking()` with no frames ever arriving and nothing
+        // logged. Watch the handle and turn that into a loud, fast process exit
+        // instead, so a supervisor sees the crash and restarts us.
+        thread::spawn(move || {
+            match capture_handle.join() {
+                Ok(()) => eprintln!("Capture thread exited unexpectedly"),
+                Err(_) => eprintln!("Capture thread panicked"),
+            }
+            std::process::exit(1);
+        });
+
+        // Sender thread (main thread): owns the ConnectionPool and drains the
+        // channel, always working on the newest captured frame.
+        loop {
+            let img = channel.pop_blocking();
 
-                if verbose {
-                    eprintln!("No framebuffer found (count: {}), waiting...", no_fb_count);
+            let send_start = Instant::now();
+            for result in pool.broadcast_image(&img, verbose) {
+                if let Err(e) = result {
+                    if verbose {
+                        eprintln!("Broadcast error: {}", e);
+                    }
                 }
+            }
+            logger.record_capture_latency(send_start.elapsed());
 
-                // Don't send any color - just wait silently
-                // The LEDs will maintain their last state or timeout naturally
-                thread::sleep(Duration::from_secs(1));
+            if verbose {
+                let stats = pool.get_stats(channel.depth(), channel.dropped_frames());
+                if stats.dropped_frames > 0 {
+                    eprintln!("Frame channel depth: {}, dropped so far: {}", stats.channel_depth, stats.dropped_frames);
+                }
             }
         }
     }
@@ -0,0 +1,165 @@
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::diagnostics::DiagnosticLogger;
+
+/// Bound on the TCP handshake in `send_http`, so a blackholed/unreachable InfluxDB
+/// host fails fast instead of blocking on the OS connect timeout (tens of seconds).
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Where a `MetricsSink` delivers batched InfluxDB line-protocol points.
+#[derive(Debug, Clone)]
+pub enum MetricsEndpoint {
+    /// `POST http://host:port/write?db=<db>`, batch as the request body.
+    Http { host: String, port: u16, db: String },
+    /// Fire-and-forget UDP datagram per flush.
+    Udp { host: String, port: u16 },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum FieldValue {
+    Int(i64),
+    Float(f64),
+}
+
+/// Buffers InfluxDB line-protocol points (`measurement,tag=val field=val <ns-ts>`)
+/// gathered by `SystemMonitor` and flushes a batch once `flush_interval_ms` has
+/// elapsed, so load average, memory, DRM client/GEM counts, per-process RSS/FD,
+/// and capture throughput can be graphed in Grafana instead of buried in a log.
+/// Never blocks the monitor thread on a send: a failed flush is logged once and
+/// the batch dropped rather than retried or queued indefinitely.
+pub struct MetricsSink {
+    endpoint: MetricsEndpoint,
+    host_tag: String,
+    buffer: Mutex<Vec<String>>,
+    last_flush: Mutex<Instant>,
+    flush_interval: Duration,
+    logger: Option<Arc<DiagnosticLogger>>,
+}
+
+impl MetricsSink {
+    pub fn new(
+        endpoint: MetricsEndpoint,
+        host_tag: String,
+        flush_interval_ms: u64,
+        logger: Option<Arc<DiagnosticLogger>>,
+    ) -> Self {
+        MetricsSink {
+            endpoint,
+            host_tag,
+            buffer: Mutex::new(Vec::new()),
+            last_flush: Mutex::new(Instant::now()),
+            flush_interval: Duration::from_millis(flush_interval_ms),
+            logger,
+        }
+    }
+
+    /// Buffer one point. `tags` are appended after the implicit `host` tag;
+    /// `fields` are rendered as `i` (integer) or bare (float) InfluxDB field values.
+    pub fn record(&self, measurement: &str, tags: &[(&str, &str)], fields: &[(&str, FieldValue)]) {
+        if fields.is_empty() {
+            return;
+        }
+
+        let mut line = format!("{},host={}", measurement, self.host_tag);
+        for (key, value) in tags {
+            line.push_str(&format!(",{}={}", key, value));
+        }
+
+        line.push(' ');
+        let rendered_fields: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| match value {
+                FieldValue::Int(n) => format!("{}={}i", key, n),
+                FieldValue::Float(n) => format!("{}={}", key, n),
+            })
+            .collect();
+        line.push_str(&rendered_fields.join(","));
+
+        let timestamp_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        line.push_str(&format!(" {}", timestamp_ns));
+
+        if let Ok(mut buffer) = self.buffer.lock() {
+            buffer.push(line);
+        }
+    }
+
+    /// Flush the batch if `flush_interval` has elapsed since the last flush.
+    pub fn maybe_flush(&self) {
+        let should_flush = {
+            let mut last_flush = self.last_flush.lock().unwrap();
+            if last_flush.elapsed() >= self.flush_interval {
+                *last_flush = Instant::now();
+                true
+            } else {
+                false
+            }
+        };
+
+        if should_flush {
+            self.flush();
+        }
+    }
+
+    fn flush(&self) {
+        let lines = {
+            let mut buffer = self.buffer.lock().unwrap();
+            if buffer.is_empty() {
+                return;
+            }
+            std::mem::take(&mut *buffer)
+        };
+
+        let batch_size = lines.len();
+        let body = lines.join("\n");
+        let endpoint = self.endpoint.clone();
+        let logger = self.logger.clone();
+
+        // Resolve, connect and send on a short-lived thread so the caller
+        // (the monitor thread) never waits on the network.
+        std::thread::spawn(move || {
+            let result = match &endpoint {
+                MetricsEndpoint::Http { host, port, db } => Self::send_http(host, *port, db, &body),
+                MetricsEndpoint::Udp { host, port } => Self::send_udp(host, *port, &body),
+            };
+
+            if let Err(e) = result {
+                let message = format!("MetricsSink: failed to flush {} point(s): {}", batch_size, e);
+                if let Some(ref logger) = logger {
+                    logger.log_warning(&message);
+                } else {
+                    eprintln!("{}", message);
+                }
+            }
+        });
+    }
+
+    fn send_http(host: &str, port: u16, db: &str, body: &str) -> std::io::Result<()> {
+        let addr = (host, port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address found for host"))?;
+        let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        stream.set_write_timeout(Some(Duration::from_millis(500)))?;
+
+        let request = format!(
+            "POST /write?db={} HTTP/1.1\r\nHost: {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            db,
+            host,
+            body.len(),
+            body
+        );
+        stream.write_all(request.as_bytes())
+    }
+
+    fn send_udp(host: &str, port: u16, body: &str) -> std::io::Result<()> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.send_to(body.as_bytes(), (host, port))?;
+        Ok(())
+    }
+}
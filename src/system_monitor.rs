@@ -2,22 +2,43 @@ use std::fs;
 use std::process::Command;
 use std::time::Duration;
 use std::thread;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use regex::RegexSet;
+use sysinfo::{Pid, System};
 use crate::diagnostics::DiagnosticLogger;
+use crate::metrics::{FieldValue, MetricsSink};
+
+/// Patterns that flag a Kodi log line worth surfacing, checked together in
+/// a single `RegexSet` pass instead of a chain of substring scans.
+const KODI_LOG_ALERT_PATTERNS: &[&str] = &[
+    r"ERROR",
+    r"(?i)drm",
+];
+
+fn kodi_log_alert_patterns() -> &'static RegexSet {
+    static PATTERNS: OnceLock<RegexSet> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        RegexSet::new(KODI_LOG_ALERT_PATTERNS).expect("valid Kodi log alert patterns")
+    })
+}
 
 pub struct SystemMonitor {
     logger: Arc<DiagnosticLogger>,
+    metrics: Option<Arc<MetricsSink>>,
     monitoring: Arc<Mutex<bool>>,
+    sys: Arc<Mutex<System>>,
 }
 
 impl SystemMonitor {
-    pub fn new(logger: Arc<DiagnosticLogger>) -> Self {
+    pub fn new(logger: Arc<DiagnosticLogger>, metrics: Option<Arc<MetricsSink>>) -> Self {
         SystemMonitor {
             logger,
+            metrics,
             monitoring: Arc::new(Mutex::new(false)),
+            sys: Arc::new(Mutex::new(System::new())),
         }
     }
-    
+
     pub fn start_monitoring(&self, interval_ms: u64) {
         {
             let mut monitoring = self.monitoring.lock().unwrap();
@@ -27,44 +48,96 @@ impl SystemMonitor {
             }
             *monitoring = true;
         }
-        
+
         let logger = Arc::clone(&self.logger);
+        let metrics = self.metrics.clone();
         let monitoring = Arc::clone(&self.monitoring);
-        
+        let sys_handle = Arc::clone(&self.sys);
+        let own_pid = std::process::id().to_string();
+
         thread::spawn(move || {
             logger.log("MONITOR", "System monitoring started");
-            
+
             while *monitoring.lock().unwrap() {
-                Self::collect_system_metrics(&logger);
-                Self::check_kodi_status(&logger);
-                Self::check_drm_status(&logger);
+                // Refresh once per tick; the sysinfo-backed checks in the next
+                // block read from this snapshot.
+                {
+                    let mut sys = sys_handle.lock().unwrap();
+                    sys.refresh_all();
+                }
+
+                {
+                    let sys = sys_handle.lock().unwrap();
+                    Self::collect_system_metrics(&logger, metrics.as_deref(), &sys);
+                    Self::check_kodi_status(&logger, metrics.as_deref(), &sys);
+                    Self::check_process_resources(&logger, metrics.as_deref(), &sys, &own_pid, "drm-vc4-grabber");
+                }
+
+                Self::check_drm_status(&logger, metrics.as_deref());
                 Self::check_memory_pressure(&logger);
-                
+
+                if let Some(ref sink) = metrics {
+                    sink.record("drm_grabber", &[], &[("capture_count", FieldValue::Int(logger.capture_count() as i64))]);
+                    sink.maybe_flush();
+                }
+
                 thread::sleep(Duration::from_millis(interval_ms));
             }
-            
+
             logger.log("MONITOR", "System monitoring stopped");
         });
     }
-    
+
     pub fn stop_monitoring(&self) {
         let mut monitoring = self.monitoring.lock().unwrap();
         *monitoring = false;
         self.logger.log("MONITOR", "System monitoring stop requested");
     }
-    
-    fn collect_system_metrics(logger: &DiagnosticLogger) {
-        // CPU usage
-        if let Ok(loadavg) = fs::read_to_string("/proc/loadavg") {
-            let load = loadavg.split_whitespace().next().unwrap_or("unknown");
-            logger.log("SYSTEM", &format!("Load average: {}", load));
+
+    fn collect_system_metrics(logger: &DiagnosticLogger, metrics: Option<&MetricsSink>, sys: &System) {
+        // CPU usage - global utilization across all cores, plus the classic
+        // load average (sysinfo reads /proc/loadavg itself on Linux).
+        let load = System::load_average();
+        logger.log("SYSTEM", &format!("Load average: {:.2} (1m) {:.2} (5m) {:.2} (15m), CPU: {:.1}%",
+                                     load.one, load.five, load.fifteen, sys.global_cpu_usage()));
+
+        if let Some(sink) = metrics {
+            sink.record("drm_grabber", &[], &[
+                ("load_average", FieldValue::Float(load.one)),
+                ("cpu_pct", FieldValue::Float(sys.global_cpu_usage() as f64)),
+            ]);
         }
-        
+
         // Memory usage
+        let mem_total = sys.total_memory();
+        let mem_used = sys.used_memory();
+        let swap_total = sys.total_swap();
+        let swap_used = sys.used_swap();
+
+        if mem_total > 0 {
+            let mem_used_pct = (mem_used * 100) / mem_total;
+            logger.log("SYSTEM", &format!("Memory usage: {}% ({}/{} MB), Swap: {}/{} MB",
+                                         mem_used_pct,
+                                         mem_used / 1024 / 1024,
+                                         mem_total / 1024 / 1024,
+                                         swap_used / 1024 / 1024,
+                                         swap_total / 1024 / 1024));
+
+            if let Some(sink) = metrics {
+                sink.record("drm_grabber", &[], &[("mem_used_pct", FieldValue::Int(mem_used_pct as i64))]);
+            }
+        } else {
+            // sysinfo couldn't read memory (e.g. unsupported platform) - fall back
+            // to the minimal Pi userland's /proc/meminfo.
+            Self::collect_memory_from_proc(logger, metrics);
+        }
+    }
+
+    fn collect_memory_from_proc(logger: &DiagnosticLogger, metrics: Option<&MetricsSink>) {
         if let Ok(meminfo) = fs::read_to_string("/proc/meminfo") {
             let mut mem_total = 0;
             let mut mem_available = 0;
-            
+
             for line in meminfo.lines() {
                 if line.starts_with("MemTotal:") {
                     mem_total = Self::extract_kb_value(line);
@@ -72,24 +145,28 @@ impl SystemMonitor {
                     mem_available = Self::extract_kb_value(line);
                 }
             }
-            
+
             if mem_total > 0 {
                 let mem_used_pct = ((mem_total - mem_available) * 100) / mem_total;
-                logger.log("SYSTEM", &format!("Memory usage: {}% ({}/{} MB)", 
-                                             mem_used_pct, 
+                logger.log("SYSTEM", &format!("Memory usage: {}% ({}/{} MB)",
+                                             mem_used_pct,
                                              (mem_total - mem_available) / 1024,
                                              mem_total / 1024));
+
+                if let Some(sink) = metrics {
+                    sink.record("drm_grabber", &[], &[("mem_used_pct", FieldValue::Int(mem_used_pct as i64))]);
+                }
             }
         }
     }
-    
-    fn check_kodi_status(logger: &DiagnosticLogger) {
+
+    fn check_kodi_status(logger: &DiagnosticLogger, metrics: Option<&MetricsSink>, sys: &System) {
         // Check if Kodi is running
         let output = Command::new("pgrep")
             .arg("-f")
             .arg("kodi")
             .output();
-            
+
         match output {
             Ok(result) if result.status.success() => {
                 let stdout_str = String::from_utf8_lossy(&result.stdout);
@@ -98,21 +175,21 @@ impl SystemMonitor {
                     .lines()
                     .collect();
                 logger.log("KODI", &format!("Running (PIDs: {})", pids.join(", ")));
-                
+
                 // Check Kodi's resource usage
                 for pid in &pids {
-                    Self::check_process_resources(logger, pid, "kodi");
+                    Self::check_process_resources(logger, metrics, sys, pid, "kodi");
                 }
             }
             _ => {
                 logger.log("KODI", "Not running");
             }
         }
-        
+
         // Check Kodi logs for recent errors
         Self::check_kodi_logs(logger);
     }
-    
+
     fn check_kodi_logs(logger: &DiagnosticLogger) {
         // Check for recent Kodi crashes or DRM errors
         let log_paths = [
@@ -120,30 +197,43 @@ impl SystemMonitor {
             "/home/osmc/.kodi/temp/kodi.log",
             "/storage/.kodi/temp/kodi.log",
         ];
-        
+
+        let patterns = kodi_log_alert_patterns();
+
         for log_path in &log_paths {
             if let Ok(output) = Command::new("tail")
                 .arg("-n")
                 .arg("10")
                 .arg(log_path)
                 .output() {
-                
+
                 let log_content = String::from_utf8_lossy(&output.stdout);
-                if log_content.contains("ERROR") || log_content.contains("drm") || log_content.contains("DRM") {
-                    logger.log("KODI_LOG", &format!("Recent errors in {}: {}", 
-                                                   log_path, 
-                                                   log_content.lines().last().unwrap_or("unknown")));
+                for line in log_content.lines() {
+                    let matched: Vec<&str> = patterns
+                        .matches(line)
+                        .into_iter()
+                        .map(|i| KODI_LOG_ALERT_PATTERNS[i])
+                        .collect();
+
+                    if !matched.is_empty() {
+                        logger.log("KODI_LOG", &format!("Recent alert in {} (matched {}): {}",
+                                                       log_path, matched.join(","), line));
+                    }
                 }
             }
         }
     }
-    
-    fn check_drm_status(logger: &DiagnosticLogger) {
+
+    fn check_drm_status(logger: &DiagnosticLogger, metrics: Option<&MetricsSink>) {
         // Check DRM clients
         if let Ok(clients) = fs::read_to_string("/sys/kernel/debug/dri/0/clients") {
             let client_count = clients.lines().count().saturating_sub(1); // Subtract header
             logger.log("DRM", &format!("Active clients: {}", client_count));
-            
+
+            if let Some(sink) = metrics {
+                sink.record("drm_grabber", &[], &[("drm_clients", FieldValue::Int(client_count as i64))]);
+            }
+
             // Log client details if verbose
             for (i, line) in clients.lines().enumerate() {
                 if i > 0 && i <= 5 { // Skip header, show first 5 clients
@@ -151,24 +241,28 @@ impl SystemMonitor {
                 }
             }
         }
-        
+
         // Check GEM objects
         if let Ok(gem_names) = fs::read_to_string("/sys/kernel/debug/dri/0/gem_names") {
             let gem_count = gem_names.lines().count().saturating_sub(1);
             logger.log("DRM", &format!("GEM objects: {}", gem_count));
-            
+
+            if let Some(sink) = metrics {
+                sink.record("drm_grabber", &[], &[("gem_objects", FieldValue::Int(gem_count as i64))]);
+            }
+
             if gem_count > 100 {
                 logger.log_warning(&format!("High GEM object count: {}", gem_count));
             }
         }
-        
+
         // Check for DRM errors in dmesg
         if let Ok(output) = Command::new("dmesg")
             .arg("-T")
             .arg("--since")
             .arg("1 minute ago")
             .output() {
-            
+
             let dmesg_content = String::from_utf8_lossy(&output.stdout);
             for line in dmesg_content.lines() {
                 if line.contains("drm") || line.contains("vc4") {
@@ -179,7 +273,7 @@ impl SystemMonitor {
             }
         }
     }
-    
+
     fn check_memory_pressure(logger: &DiagnosticLogger) {
         // Check for OOM killer activity
         if let Ok(output) = Command::new("dmesg")
@@ -187,7 +281,7 @@ impl SystemMonitor {
             .arg("--since")
             .arg("1 minute ago")
             .output() {
-            
+
             let dmesg_content = String::from_utf8_lossy(&output.stdout);
             for line in dmesg_content.lines() {
                 if line.contains("Out of memory") || line.contains("oom-killer") {
@@ -195,7 +289,7 @@ impl SystemMonitor {
                 }
             }
         }
-        
+
         // Check memory pressure indicators
         if let Ok(pressure) = fs::read_to_string("/proc/pressure/memory") {
             for line in pressure.lines() {
@@ -205,35 +299,72 @@ impl SystemMonitor {
             }
         }
     }
-    
-    fn check_process_resources(logger: &DiagnosticLogger, pid: &str, process_name: &str) {
-        // Check process memory usage
-        if let Ok(status) = fs::read_to_string(format!("/proc/{}/status", pid)) {
-            for line in status.lines() {
-                if line.starts_with("VmRSS:") {
-                    let rss_kb = Self::extract_kb_value(line);
-                    logger.log("PROC", &format!("{} (PID {}): RSS {} MB", 
-                                               process_name, pid, rss_kb / 1024));
+
+    fn check_process_resources(logger: &DiagnosticLogger, metrics: Option<&MetricsSink>, sys: &System, pid: &str, process_name: &str) {
+        let process = pid.parse::<usize>().ok()
+            .and_then(|raw_pid| sys.process(Pid::from(raw_pid)));
+
+        match process {
+            Some(process) => {
+                let rss_kb = process.memory() / 1024;
+                let cpu_pct = process.cpu_usage();
+                let run_time_secs = process.run_time();
+
+                logger.log("PROC", &format!("{} (PID {}): RSS {} MB, CPU {:.1}%, uptime {}s",
+                                           process_name, pid, rss_kb / 1024, cpu_pct, run_time_secs));
+
+                if let Some(sink) = metrics {
+                    sink.record("drm_grabber_proc", &[("process", process_name), ("pid", pid)], &[
+                        ("rss_kb", FieldValue::Int(rss_kb as i64)),
+                        ("cpu_pct", FieldValue::Float(cpu_pct as f64)),
+                    ]);
                 }
             }
+            None => {
+                // sysinfo couldn't find/read this PID (short-lived process, or an
+                // unsupported platform) - fall back to the raw /proc files.
+                Self::check_process_resources_from_proc(logger, metrics, pid, process_name);
+            }
         }
-        
-        // Check file descriptor usage
+
+        // File descriptor usage isn't exposed by sysinfo; read it directly.
         if let Ok(fd_dir) = fs::read_dir(format!("/proc/{}/fd", pid)) {
             let fd_count = fd_dir.count();
-            logger.log("PROC", &format!("{} (PID {}): {} file descriptors", 
+            logger.log("PROC", &format!("{} (PID {}): {} file descriptors",
                                        process_name, pid, fd_count));
-            
+
+            if let Some(sink) = metrics {
+                sink.record("drm_grabber_proc", &[("process", process_name), ("pid", pid)],
+                            &[("fd_count", FieldValue::Int(fd_count as i64))]);
+            }
+
             if fd_count > 500 {
                 logger.log_warning(&format!("{} has high FD count: {}", process_name, fd_count));
             }
         }
     }
-    
+
+    fn check_process_resources_from_proc(logger: &DiagnosticLogger, metrics: Option<&MetricsSink>, pid: &str, process_name: &str) {
+        if let Ok(status) = fs::read_to_string(format!("/proc/{}/status", pid)) {
+            for line in status.lines() {
+                if line.starts_with("VmRSS:") {
+                    let rss_kb = Self::extract_kb_value(line);
+                    logger.log("PROC", &format!("{} (PID {}): RSS {} MB",
+                                               process_name, pid, rss_kb / 1024));
+
+                    if let Some(sink) = metrics {
+                        sink.record("drm_grabber_proc", &[("process", process_name), ("pid", pid)],
+                                    &[("rss_kb", FieldValue::Int(rss_kb as i64))]);
+                    }
+                }
+            }
+        }
+    }
+
     fn extract_kb_value(line: &str) -> u64 {
         line.split_whitespace()
             .nth(1)
             .and_then(|s| s.parse().ok())
             .unwrap_or(0)
     }
-}
\ No newline at end of file
+}